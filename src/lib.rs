@@ -2,16 +2,31 @@ use std::fmt::Debug;
 use std::io::Error;
 use std::net::{TcpStream, ToSocketAddrs};
 use std::ops::Deref;
+use std::panic;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
 use std::time::Duration;
 
+use foreign_types::ForeignTypeRef;
 use openssl::asn1::{Asn1Time, Asn1TimeRef};
 use openssl::error::ErrorStack;
+use openssl::hash::MessageDigest;
 use openssl::nid::Nid;
-use openssl::ssl::{HandshakeError, Ssl, SslContext, SslMethod, SslVerifyMode};
-use openssl::x509::{X509NameEntries, X509};
+use openssl::ssl::{
+    HandshakeError, Ssl, SslContext, SslMethod, SslStream, SslVerifyMode, SslVersion,
+};
+use openssl::stack::Stack;
+use openssl::x509::store::X509StoreBuilder;
+use openssl::x509::{X509NameEntries, X509Ref, X509StoreContext, X509VerifyResult, X509};
 use serde::{Deserialize, Serialize};
 
 static TIMEOUT: Duration = Duration::from_secs(30);
+static DEFAULT_CONCURRENCY: usize = 16;
+
+/// A single [`Certificate::check_many`] outcome: the host it was checked
+/// against and the result of that check.
+type HostResult = (String, Result<Certificate, TLSValidationError>);
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Chain {
@@ -20,6 +35,8 @@ pub struct Chain {
     pub valid_from: String,
     pub valid_to: String,
     pub signature_algorithm: String,
+    pub sha1_fingerprint: String,
+    pub sha256_fingerprint: String,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -36,6 +53,14 @@ pub struct Certificate {
     pub cert_ver: String,
     pub cert_alg: String,
     pub sans: Vec<String>,
+    pub sha1_fingerprint: String,
+    pub sha256_fingerprint: String,
+    pub negotiated_protocol: String,
+    pub cipher_suite: String,
+    pub key_usage: Vec<String>,
+    pub extended_key_usage: Vec<String>,
+    pub is_ca: bool,
+    pub path_len_constraint: Option<u32>,
     pub chain: Option<Vec<Chain>>,
 }
 
@@ -56,29 +81,80 @@ pub struct Subject {
     pub common_name: String,
 }
 
-impl Certificate {
-    pub fn from(host: &str) -> Result<Certificate, TLSValidationError> {
-        let mut context = SslContext::builder(SslMethod::tls())?;
-        context.set_verify(SslVerifyMode::empty());
-        let context_builder = context.build();
+/// Connection options for [`Certificate::from_with_options`].
+pub struct CertificateOptions {
+    pub port: u16,
+    pub timeout: Duration,
+    pub server_name: Option<String>,
+    pub min_protocol: Option<SslVersion>,
+    pub max_protocol: Option<SslVersion>,
+}
+
+impl Default for CertificateOptions {
+    fn default() -> CertificateOptions {
+        CertificateOptions {
+            port: 443,
+            timeout: TIMEOUT,
+            server_name: None,
+            min_protocol: None,
+            max_protocol: None,
+        }
+    }
+}
 
-        let mut connector = Ssl::new(&context_builder)?;
-        connector.set_hostname(host)?;
+fn connect(
+    host: &str,
+    options: &CertificateOptions,
+) -> Result<SslStream<TcpStream>, TLSValidationError> {
+    let server_name = options.server_name.as_deref().unwrap_or(host);
 
-        let remote = format!("{host}:443");
-        let socket_addr = remote
-            .to_socket_addrs()?
-            .next()
-            .ok_or("Failed parse remote hostname")?;
+    let mut context = SslContext::builder(SslMethod::tls())?;
+    context.set_verify(SslVerifyMode::empty());
+    if let Some(min_protocol) = options.min_protocol {
+        context.set_min_proto_version(Some(min_protocol))?;
+    }
+    if let Some(max_protocol) = options.max_protocol {
+        context.set_max_proto_version(Some(max_protocol))?;
+    }
+    let context_builder = context.build();
+
+    let mut connector = Ssl::new(&context_builder)?;
+    connector.set_hostname(server_name)?;
+
+    let remote = format!("{host}:{}", options.port);
+    let socket_addr = remote
+        .to_socket_addrs()?
+        .next()
+        .ok_or("Failed parse remote hostname")?;
+
+    let tcp_stream = TcpStream::connect_timeout(&socket_addr, options.timeout)?;
+    tcp_stream.set_read_timeout(Some(options.timeout))?;
+
+    Ok(connector.connect(tcp_stream)?)
+}
 
-        let tcp_stream = TcpStream::connect_timeout(&socket_addr, TIMEOUT)?;
+impl Certificate {
+    pub fn from(host: &str) -> Result<Certificate, TLSValidationError> {
+        Certificate::from_with_options(host, &CertificateOptions::default())
+    }
 
-        tcp_stream.set_read_timeout(Some(TIMEOUT))?;
-        let stream = connector.connect(tcp_stream)?;
+    /// Like [`Certificate::from`], but with a caller-supplied port, SNI name,
+    /// timeout, and protocol version range.
+    pub fn from_with_options(
+        host: &str,
+        options: &CertificateOptions,
+    ) -> Result<Certificate, TLSValidationError> {
+        let stream = connect(host, options)?;
 
         // `Ssl` object associated with this stream
         let ssl = stream.ssl();
 
+        let negotiated_protocol = ssl.version_str().to_string();
+        let cipher_suite = ssl
+            .current_cipher()
+            .map(|cipher| cipher.name().to_string())
+            .unwrap_or_default();
+
         let peer_cert_chain = ssl
             .peer_cert_chain()
             .ok_or("Peer certificate chain not found")?
@@ -89,6 +165,8 @@ impl Certificate {
                 valid_from: chain.not_before().to_string(),
                 issuer: from_entries(chain.issuer_name().entries_by_nid(Nid::COMMONNAME)),
                 signature_algorithm: chain.signature_algorithm().object().to_string(),
+                sha1_fingerprint: digest_hex(chain, MessageDigest::sha1()),
+                sha256_fingerprint: digest_hex(chain, MessageDigest::sha256()),
             })
             .collect::<Vec<Chain>>();
 
@@ -108,10 +186,190 @@ impl Certificate {
             cert_ver: data.cert_ver,
             cert_alg: data.cert_alg,
             sans: data.sans,
+            sha1_fingerprint: data.sha1_fingerprint,
+            sha256_fingerprint: data.sha256_fingerprint,
+            negotiated_protocol,
+            cipher_suite,
+            key_usage: data.key_usage,
+            extended_key_usage: data.extended_key_usage,
+            is_ca: data.is_ca,
+            path_len_constraint: data.path_len_constraint,
             chain: Some(peer_cert_chain),
         };
         Ok(certificate)
     }
+
+    /// Parses a certificate from a PEM-encoded byte slice without making any
+    /// network connection, so bundled certs can be validated in CI.
+    /// `hostname` is reported as `"None"` since there's no live handshake.
+    pub fn from_pem(pem: &[u8]) -> Result<Certificate, TLSValidationError> {
+        let cert = X509::from_pem(pem)?;
+        Ok(get_certificate_info(&cert))
+    }
+
+    /// Like [`Certificate::from_pem`], but for DER-encoded input.
+    pub fn from_der(der: &[u8]) -> Result<Certificate, TLSValidationError> {
+        let cert = X509::from_der(der)?;
+        Ok(get_certificate_info(&cert))
+    }
+
+    /// Checks many hosts concurrently over a bounded thread pool, isolating
+    /// per-host errors (including panics) so a single bad host doesn't abort
+    /// the batch.
+    pub fn check_many(hosts: &[&str]) -> Vec<HostResult> {
+        Certificate::check_many_with_concurrency(hosts, DEFAULT_CONCURRENCY)
+    }
+
+    /// Like [`Certificate::check_many`], but lets callers size the worker pool.
+    pub fn check_many_with_concurrency(hosts: &[&str], concurrency: usize) -> Vec<HostResult> {
+        let concurrency = concurrency.max(1).min(hosts.len().max(1));
+        let next_host = AtomicUsize::new(0);
+        let results: Mutex<Vec<Option<HostResult>>> =
+            Mutex::new((0..hosts.len()).map(|_| None).collect());
+
+        thread::scope(|scope| {
+            for _ in 0..concurrency {
+                scope.spawn(|| loop {
+                    let index = next_host.fetch_add(1, Ordering::SeqCst);
+                    if index >= hosts.len() {
+                        break;
+                    }
+                    let host = hosts[index];
+                    let result = panic::catch_unwind(|| Certificate::from(host))
+                        .unwrap_or_else(|_| Err("panicked while checking this host".into()));
+                    results.lock().unwrap()[index] = Some((host.to_string(), result));
+                });
+            }
+        });
+
+        results
+            .into_inner()
+            .unwrap()
+            .into_iter()
+            .map(|slot| slot.expect("every index is visited exactly once"))
+            .collect()
+    }
+
+    /// Verifies `host`'s certificate chain against the system's default trust
+    /// store, reporting a typed [`TrustError`] instead of just failing to
+    /// connect.
+    pub fn verify(host: &str) -> Result<TrustResult, TLSValidationError> {
+        Certificate::verify_with_roots(host, &CertificateOptions::default(), &[])
+    }
+
+    /// Like [`Certificate::verify`], but lets callers override the connection
+    /// via `options` and also trusts the PEM-encoded roots in `extra_roots`
+    /// in addition to the system default paths.
+    pub fn verify_with_roots(
+        host: &str,
+        options: &CertificateOptions,
+        extra_roots: &[Vec<u8>],
+    ) -> Result<TrustResult, TLSValidationError> {
+        let stream = connect(host, options)?;
+        let ssl = stream.ssl();
+
+        let peer_cert_chain = ssl
+            .peer_cert_chain()
+            .ok_or("Peer certificate chain not found")?;
+        let leaf = ssl.peer_certificate().ok_or("Certificate not found")?;
+
+        let mut store_builder = X509StoreBuilder::new()?;
+        store_builder.set_default_paths()?;
+        for root_pem in extra_roots {
+            store_builder.add_cert(X509::from_pem(root_pem)?)?;
+        }
+        let store = store_builder.build();
+
+        let mut chain_stack = Stack::new()?;
+        for cert in peer_cert_chain {
+            chain_stack.push(cert.to_owned())?;
+        }
+
+        let mut store_ctx = X509StoreContext::new()?;
+        let (verified, verify_result) = store_ctx.init(&store, &leaf, &chain_stack, |ctx| {
+            let verified = ctx.verify_cert()?;
+            Ok((verified, ctx.error()))
+        })?;
+
+        let hostname_matches = leaf
+            .subject_alt_names()
+            .map(|sans| {
+                sans.iter().any(|san| {
+                    san.dnsname()
+                        .is_some_and(|pattern| hostname_matches_pattern(host, pattern))
+                })
+            })
+            .unwrap_or(false)
+            || hostname_matches_pattern(
+                host,
+                &from_entries(leaf.subject_name().entries_by_nid(Nid::COMMONNAME)),
+            );
+
+        let trust_error = if !verified {
+            Some(TrustError::from(verify_result))
+        } else if !hostname_matches {
+            Some(TrustError::HostnameMismatch)
+        } else {
+            None
+        };
+
+        Ok(TrustResult {
+            hostname: host.to_string(),
+            trusted: trust_error.is_none(),
+            error: trust_error,
+        })
+    }
+}
+
+/// Outcome of [`Certificate::verify`]: whether the chain is trusted, and if
+/// not, a typed reason rather than an opaque string.
+#[derive(Debug, Clone)]
+pub struct TrustResult {
+    pub hostname: String,
+    pub trusted: bool,
+    pub error: Option<TrustError>,
+}
+
+/// Reasons [`Certificate::verify`] can report a chain as untrusted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TrustError {
+    Expired,
+    HostnameMismatch,
+    UntrustedRoot,
+    SelfSigned,
+    Revoked,
+    Other(String),
+}
+
+impl From<X509VerifyResult> for TrustError {
+    fn from(result: X509VerifyResult) -> TrustError {
+        match result.error_string() {
+            "certificate has expired" => TrustError::Expired,
+            "self signed certificate" | "self signed certificate in certificate chain" => {
+                TrustError::SelfSigned
+            }
+            "unable to get local issuer certificate"
+            | "unable to get issuer certificate"
+            | "unable to verify the first certificate" => TrustError::UntrustedRoot,
+            "certificate revoked" => TrustError::Revoked,
+            other => TrustError::Other(other.to_string()),
+        }
+    }
+}
+
+/// Matches `host` against a certificate DNS name `pattern`, allowing a single
+/// leftmost-label wildcard (`*.example.com`) per RFC 6125 section 6.4.3.
+fn hostname_matches_pattern(host: &str, pattern: &str) -> bool {
+    match pattern.strip_prefix("*.") {
+        Some(wildcard_suffix) => {
+            let mut labels = host.splitn(2, '.');
+            let first_label = labels.next().unwrap_or("");
+            let suffix = labels.next();
+            !first_label.is_empty()
+                && suffix.is_some_and(|s| s.eq_ignore_ascii_case(wildcard_suffix))
+        }
+        None => host.eq_ignore_ascii_case(pattern),
+    }
 }
 
 fn from_entries(mut entries: X509NameEntries) -> String {
@@ -161,7 +419,9 @@ fn get_certificate_info(cert_ref: &X509) -> Certificate {
         None => {}
         Some(general_names) => {
             for general_name in general_names {
-                sans.push(general_name.dnsname().unwrap().to_string());
+                if let Some(dnsname) = general_name.dnsname() {
+                    sans.push(dnsname.to_string());
+                }
             }
         }
     }
@@ -178,10 +438,79 @@ fn get_certificate_info(cert_ref: &X509) -> Certificate {
         cert_ver: cert_ref.version().to_string(),
         cert_alg: cert_ref.signature_algorithm().object().to_string(),
         sans,
+        sha1_fingerprint: digest_hex(cert_ref, MessageDigest::sha1()),
+        sha256_fingerprint: digest_hex(cert_ref, MessageDigest::sha256()),
+        negotiated_protocol: "None".to_string(),
+        cipher_suite: "None".to_string(),
+        key_usage: parse_key_usage(cert_ref),
+        extended_key_usage: parse_extended_key_usage(cert_ref),
+        is_ca: is_ca(cert_ref),
+        path_len_constraint: cert_ref.pathlen(),
         chain: None,
     };
 }
 
+fn digest_hex(cert_ref: &X509Ref, digest: MessageDigest) -> String {
+    cert_ref
+        .digest(digest)
+        .map(|bytes| bytes.iter().map(|byte| format!("{byte:02x}")).collect())
+        .unwrap_or_default()
+}
+
+// openssl-rs doesn't expose safe readers for the X509v3 key usage, extended
+// key usage, and CA extension bits, so we read them via the same ffi calls it
+// uses internally for `pathlen`/`subject_key_id`.
+
+fn parse_key_usage(cert_ref: &X509Ref) -> Vec<String> {
+    const FLAGS: &[(u32, &str)] = &[
+        (openssl_sys::X509v3_KU_DIGITAL_SIGNATURE, "digitalSignature"),
+        (openssl_sys::X509v3_KU_NON_REPUDIATION, "nonRepudiation"),
+        (openssl_sys::X509v3_KU_KEY_ENCIPHERMENT, "keyEncipherment"),
+        (openssl_sys::X509v3_KU_DATA_ENCIPHERMENT, "dataEncipherment"),
+        (openssl_sys::X509v3_KU_KEY_AGREEMENT, "keyAgreement"),
+        (openssl_sys::X509v3_KU_KEY_CERT_SIGN, "keyCertSign"),
+        (openssl_sys::X509v3_KU_CRL_SIGN, "cRLSign"),
+        (openssl_sys::X509v3_KU_ENCIPHER_ONLY, "encipherOnly"),
+        (openssl_sys::X509v3_KU_DECIPHER_ONLY, "decipherOnly"),
+    ];
+
+    let bits = unsafe { openssl_sys::X509_get_key_usage(cert_ref.as_ptr()) };
+    if bits == u32::MAX {
+        return Vec::new();
+    }
+    FLAGS
+        .iter()
+        .filter(|(flag, _)| bits & flag != 0)
+        .map(|(_, name)| name.to_string())
+        .collect()
+}
+
+fn parse_extended_key_usage(cert_ref: &X509Ref) -> Vec<String> {
+    const FLAGS: &[(u32, &str)] = &[
+        (openssl_sys::XKU_SSL_SERVER, "serverAuth"),
+        (openssl_sys::XKU_SSL_CLIENT, "clientAuth"),
+        (openssl_sys::XKU_SMIME, "emailProtection"),
+        (openssl_sys::XKU_CODE_SIGN, "codeSigning"),
+        (openssl_sys::XKU_SGC, "netscapeSGC"),
+        (openssl_sys::XKU_OCSP_SIGN, "OCSPSigning"),
+        (openssl_sys::XKU_TIMESTAMP, "timeStamping"),
+        (openssl_sys::XKU_DVCS, "DVCS"),
+        (openssl_sys::XKU_ANYEKU, "anyExtendedKeyUsage"),
+    ];
+
+    let bits = unsafe { openssl_sys::X509_get_extended_key_usage(cert_ref.as_ptr()) };
+    FLAGS
+        .iter()
+        .filter(|(flag, _)| bits & flag != 0)
+        .map(|(_, name)| name.to_string())
+        .collect()
+}
+
+fn is_ca(cert_ref: &X509Ref) -> bool {
+    let flags = unsafe { openssl_sys::X509_get_extension_flags(cert_ref.as_ptr()) };
+    flags & openssl_sys::EXFLAG_CA != 0
+}
+
 fn get_validity_in_hours(not_after: &Asn1TimeRef) -> i32 {
     get_validity_days(not_after) * 24
 }
@@ -238,7 +567,85 @@ impl<S> From<HandshakeError<S>> for TLSValidationError {
 
 #[cfg(test)]
 mod tests {
-    use crate::Certificate;
+    use crate::{hostname_matches_pattern, Certificate, CertificateOptions, TrustError};
+    use openssl::ssl::SslVersion;
+
+    #[test]
+    fn test_hostname_matches_pattern_exact() {
+        assert!(hostname_matches_pattern("jpbd.dev", "jpbd.dev"));
+        assert!(hostname_matches_pattern("JPBD.dev", "jpbd.dev"));
+        assert!(!hostname_matches_pattern("other.dev", "jpbd.dev"));
+    }
+
+    #[test]
+    fn test_hostname_matches_pattern_wildcard() {
+        assert!(hostname_matches_pattern("www.badssl.com", "*.badssl.com"));
+        assert!(!hostname_matches_pattern("badssl.com", "*.badssl.com"));
+        assert!(!hostname_matches_pattern("a.b.badssl.com", "*.badssl.com"));
+    }
+
+    #[test]
+    fn test_verify_trusts_valid_host() {
+        let result = Certificate::verify("jpbd.dev").unwrap();
+        assert!(result.trusted);
+        assert!(result.error.is_none());
+    }
+
+    #[test]
+    fn test_verify_rejects_self_signed_host() {
+        let result = Certificate::verify("self-signed.badssl.com").unwrap();
+        assert!(!result.trusted);
+        assert_eq!(result.error, Some(TrustError::SelfSigned));
+    }
+
+    const FIXTURE_CERT_PEM: &[u8] = include_bytes!("../test/fixture_cert.pem");
+    const FIXTURE_CERT_DER: &[u8] = include_bytes!("../test/fixture_cert.der");
+
+    #[test]
+    fn test_from_pem_parses_fixture_cert() {
+        let cert = Certificate::from_pem(FIXTURE_CERT_PEM).unwrap();
+        assert_eq!(cert.hostname, "None");
+        assert_eq!(cert.subject.common_name, "tlschecker-fixture.example");
+        assert_eq!(cert.sans, vec!["tlschecker-fixture.example".to_string()]);
+        assert_eq!(
+            cert.cert_sn,
+            "517138195081230116515712988988134593795958578043"
+        );
+        assert_eq!(
+            cert.sha256_fingerprint,
+            "f3db4b32595b42fe40a6a81e2e1c46357cfb266e79f7a3f05037a2aaf88cc5f8"
+        );
+        assert_eq!(
+            cert.sha1_fingerprint,
+            "8bf0ee0712b7f85ef6bd6a810579b922f2e08474"
+        );
+        assert!(cert.chain.is_none());
+    }
+
+    #[test]
+    fn test_from_der_parses_fixture_cert() {
+        let cert = Certificate::from_der(FIXTURE_CERT_DER).unwrap();
+        assert_eq!(cert.subject.common_name, "tlschecker-fixture.example");
+        assert_eq!(
+            cert.sha256_fingerprint,
+            "f3db4b32595b42fe40a6a81e2e1c46357cfb266e79f7a3f05037a2aaf88cc5f8"
+        );
+    }
+
+    #[test]
+    fn test_from_pem_parses_key_usage_extended_key_usage_and_is_ca() {
+        let cert = Certificate::from_pem(FIXTURE_CERT_PEM).unwrap();
+        assert_eq!(
+            cert.key_usage,
+            vec![
+                "digitalSignature".to_string(),
+                "keyEncipherment".to_string()
+            ]
+        );
+        assert_eq!(cert.extended_key_usage, vec!["serverAuth".to_string()]);
+        assert!(!cert.is_ca);
+        assert_eq!(cert.path_len_constraint, None);
+    }
 
     #[test]
     fn test_check_tls_for_expired_host() {
@@ -274,9 +681,31 @@ mod tests {
         assert_eq!(cert.cert_ver, "2");
         assert_eq!(cert.sans.len(), 2);
         assert_eq!(cert.hostname, host);
+        assert!(!cert.negotiated_protocol.is_empty());
+        assert!(!cert.cipher_suite.is_empty());
         assert!(!cert.chain.unwrap().is_empty());
     }
 
+    #[test]
+    fn test_check_tls_respects_max_protocol_version() {
+        let options = CertificateOptions {
+            max_protocol: Some(SslVersion::TLS1_2),
+            ..CertificateOptions::default()
+        };
+        let cert = Certificate::from_with_options("jpbd.dev", &options).unwrap();
+        assert_eq!(cert.negotiated_protocol, "TLSv1.2");
+    }
+
+    #[test]
+    fn test_from_with_options_respects_custom_port() {
+        let options = CertificateOptions {
+            port: 80,
+            ..CertificateOptions::default()
+        };
+        let result = Certificate::from_with_options("jpbd.dev", &options);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_check_tls_for_valid_host_without_sans() {
         let host = "acme-staging-v02.api.letsencrypt.org";
@@ -318,4 +747,23 @@ mod tests {
         assert!(!message.is_empty());
         println!("{}", message);
     }
+
+    #[test]
+    fn test_check_many_isolates_per_host_errors() {
+        let hosts = ["basdomain.xyz", "expired.badssl.com"];
+        let results = Certificate::check_many(&hosts);
+        assert_eq!(results.len(), hosts.len());
+
+        let (_, bad_result) = results
+            .iter()
+            .find(|(host, _)| host == "basdomain.xyz")
+            .unwrap();
+        assert!(bad_result.is_err());
+
+        let (_, good_result) = results
+            .iter()
+            .find(|(host, _)| host == "expired.badssl.com")
+            .unwrap();
+        assert!(good_result.as_ref().unwrap().is_expired);
+    }
 }